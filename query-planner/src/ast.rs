@@ -0,0 +1,44 @@
+//! `graphql_parser` 0.3+ made every AST node generic over a `Text<'a>` marker type so
+//! callers can choose a borrowed or owned string representation. This crate only ever
+//! wants the borrowed one, so this module re-exports the schema/query AST pinned to
+//! `&'a str`, giving the rest of the crate back the single-lifetime `Foo<'a>` shapes
+//! it's written against instead of threading a second generic parameter everywhere.
+
+pub mod schema {
+    #[cfg(test)]
+    pub use graphql_parser::schema::ParseError;
+    pub use graphql_parser::Pos;
+
+    pub type Document<'a> = graphql_parser::schema::Document<'a, &'a str>;
+    pub type Definition<'a> = graphql_parser::schema::Definition<'a, &'a str>;
+    pub type TypeDefinition<'a> = graphql_parser::schema::TypeDefinition<'a, &'a str>;
+    pub type TypeExtension<'a> = graphql_parser::schema::TypeExtension<'a, &'a str>;
+    pub type ObjectType<'a> = graphql_parser::schema::ObjectType<'a, &'a str>;
+    pub type ObjectTypeExtension<'a> = graphql_parser::schema::ObjectTypeExtension<'a, &'a str>;
+    pub type ScalarType<'a> = graphql_parser::schema::ScalarType<'a, &'a str>;
+    pub type UnionType<'a> = graphql_parser::schema::UnionType<'a, &'a str>;
+    pub type Field<'a> = graphql_parser::schema::Field<'a, &'a str>;
+    pub type InputValue<'a> = graphql_parser::schema::InputValue<'a, &'a str>;
+    pub type Directive<'a> = graphql_parser::schema::Directive<'a, &'a str>;
+    pub type Value<'a> = graphql_parser::schema::Value<'a, &'a str>;
+    pub type Type<'a> = graphql_parser::schema::Type<'a, &'a str>;
+
+    #[cfg(test)]
+    pub fn parse_schema(s: &str) -> Result<Document<'_>, ParseError> {
+        graphql_parser::schema::parse_schema::<&str>(s)
+    }
+}
+
+pub mod query {
+    pub use graphql_parser::query::ParseError;
+
+    pub type Document<'a> = graphql_parser::query::Document<'a, &'a str>;
+    pub type Definition<'a> = graphql_parser::query::Definition<'a, &'a str>;
+    pub type OperationDefinition<'a> = graphql_parser::query::OperationDefinition<'a, &'a str>;
+    pub type Selection<'a> = graphql_parser::query::Selection<'a, &'a str>;
+    pub type SelectionSet<'a> = graphql_parser::query::SelectionSet<'a, &'a str>;
+
+    pub fn parse_query(s: &str) -> Result<Document<'_>, ParseError> {
+        graphql_parser::query::parse_query::<&str>(s)
+    }
+}
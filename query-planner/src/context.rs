@@ -0,0 +1,101 @@
+use crate::ast::query::{parse_query, Definition, OperationDefinition, Selection, SelectionSet};
+
+/// A parsed GraphQL field selection, as declared in a federation directive's
+/// `fields:` argument (e.g. `@key(fields: "id org { id }")`).
+///
+/// Federation encodes these selections as a fragment of GraphQL query syntax rather
+/// than a dedicated mini-language, so a `FieldSet` is the structured form of that
+/// fragment: one entry per top-level field, each carrying its own (possibly empty)
+/// sub-selection.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct FieldSet {
+    pub name: String,
+    pub selections: Vec<FieldSet>,
+}
+
+impl FieldSet {
+    /// Parses a federation `fields:` argument (e.g. `"id org { id }"`) into its
+    /// top-level `FieldSet`s.
+    ///
+    /// The argument is a bare GraphQL selection set without the enclosing braces, so
+    /// it's wrapped in `{ ... }` and fed through `graphql_parser`'s query grammar,
+    /// which also gives us comma/whitespace tolerance and nested selections for free.
+    /// A `fields:` argument that fails to parse yields no fields, since malformed
+    /// federation directives shouldn't panic the schema that declares them.
+    pub fn parse(fields: &str) -> Vec<FieldSet> {
+        let wrapped = format!("{{ {} }}", fields);
+        let document = match parse_query(&wrapped) {
+            Ok(document) => document,
+            Err(_) => return Vec::new(),
+        };
+
+        document
+            .definitions
+            .iter()
+            .find_map(|definition| match definition {
+                Definition::Operation(OperationDefinition::SelectionSet(selection_set)) => {
+                    Some(selection_set_to_field_sets(selection_set))
+                }
+                _ => None,
+            })
+            .unwrap_or_default()
+    }
+}
+
+fn selection_set_to_field_sets(selection_set: &SelectionSet) -> Vec<FieldSet> {
+    selection_set
+        .items
+        .iter()
+        .filter_map(|selection| match selection {
+            Selection::Field(field) => Some(FieldSet {
+                name: field.name.to_string(),
+                selections: selection_set_to_field_sets(&field.selection_set),
+            }),
+            _ => None,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn field(name: &str) -> FieldSet {
+        FieldSet {
+            name: name.to_string(),
+            selections: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn parses_simple_top_level_fields() {
+        assert_eq!(FieldSet::parse("id sku"), vec![field("id"), field("sku")]);
+    }
+
+    #[test]
+    fn tolerates_commas_and_extra_whitespace() {
+        let fields = FieldSet::parse("id,   sku ,  upc");
+        let names: Vec<&str> = fields.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(names, vec!["id", "sku", "upc"]);
+    }
+
+    #[test]
+    fn parses_nested_selections() {
+        let fields = FieldSet::parse("id org { id }");
+        assert_eq!(
+            fields,
+            vec![
+                field("id"),
+                FieldSet {
+                    name: "org".to_string(),
+                    selections: vec![field("id")],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn malformed_fields_argument_yields_no_fields() {
+        assert_eq!(FieldSet::parse("org { id"), Vec::<FieldSet>::new());
+    }
+}
@@ -0,0 +1,222 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use serde_json::{Map, Value};
+
+use crate::context::FieldSet;
+use crate::federation::FederationMetadata;
+
+/// One `_Any` representation as sent to `Query._entities`: a `__typename` plus the
+/// key fields a subgraph declared for that type.
+pub struct Representation {
+    pub typename: String,
+    pub fields: Map<String, Value>,
+}
+
+/// A representation matched against one of its type's declared `@key`s, ready for a
+/// caller-supplied resolver to turn into the actual entity.
+#[derive(Debug)]
+pub struct EntityResolutionRequest<'a> {
+    pub typename: &'a str,
+    pub key: &'a [FieldSet],
+    pub bound_fields: Map<String, Value>,
+}
+
+#[derive(Debug)]
+pub enum EntityResolutionError {
+    /// A representation's `__typename` doesn't match any entity this subgraph knows.
+    UnknownType(String),
+    /// A representation's fields don't satisfy any `@key` declared for its type.
+    NoMatchingKey(String),
+}
+
+impl fmt::Display for EntityResolutionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EntityResolutionError::UnknownType(typename) => {
+                write!(f, "representation names unknown type `{}`", typename)
+            }
+            EntityResolutionError::NoMatchingKey(typename) => write!(
+                f,
+                "representation's fields satisfy none of `{}`'s declared keys",
+                typename
+            ),
+        }
+    }
+}
+
+impl std::error::Error for EntityResolutionError {}
+
+/// Matches each representation's `__typename` against `entities` (every `@key` type
+/// this subgraph owns, keyed by name) and selects the `@key` its fields satisfy.
+///
+/// This is a pure dispatch step: it doesn't fetch anything, it just turns each `_Any`
+/// representation into a request a caller-supplied resolver can fulfill.
+pub fn resolve_entities<'a>(
+    representations: &'a [Representation],
+    entities: &'a HashMap<String, FederationMetadata<'a>>,
+) -> Result<Vec<EntityResolutionRequest<'a>>, EntityResolutionError> {
+    representations
+        .iter()
+        .map(|representation| resolve_one(representation, entities))
+        .collect()
+}
+
+fn resolve_one<'a>(
+    representation: &'a Representation,
+    entities: &'a HashMap<String, FederationMetadata<'a>>,
+) -> Result<EntityResolutionRequest<'a>, EntityResolutionError> {
+    let metadata = entities
+        .get(representation.typename.as_str())
+        .ok_or_else(|| EntityResolutionError::UnknownType(representation.typename.clone()))?;
+
+    let key = metadata
+        .keys
+        .iter()
+        .find(|key| key_is_satisfied_by(key, &representation.fields))
+        .ok_or_else(|| EntityResolutionError::NoMatchingKey(representation.typename.clone()))?;
+
+    let bound_fields = key
+        .iter()
+        .filter_map(|field| {
+            representation
+                .fields
+                .get(&field.name)
+                .map(|value| (field.name.clone(), value.clone()))
+        })
+        .collect();
+
+    Ok(EntityResolutionRequest {
+        typename: &representation.typename,
+        key,
+        bound_fields,
+    })
+}
+
+/// A key is satisfied only when every field it names is present *and*, for fields with
+/// a nested sub-selection (e.g. `@key(fields: "org { id }")`), the representation's
+/// value for that field is itself an object satisfying the nested selection — a bare
+/// `org` with no `id` does not satisfy `org { id }`.
+fn key_is_satisfied_by(key: &[FieldSet], fields: &Map<String, Value>) -> bool {
+    !key.is_empty() && key.iter().all(|field| field_is_satisfied_by(field, fields))
+}
+
+fn field_is_satisfied_by(field: &FieldSet, fields: &Map<String, Value>) -> bool {
+    match fields.get(&field.name) {
+        None => false,
+        Some(value) if field.selections.is_empty() => !value.is_null(),
+        Some(Value::Object(nested)) => field
+            .selections
+            .iter()
+            .all(|selection| field_is_satisfied_by(selection, nested)),
+        Some(_) => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn field(name: &str) -> FieldSet {
+        FieldSet {
+            name: name.to_string(),
+            selections: Vec::new(),
+        }
+    }
+
+    fn product_metadata<'a>(keys: Vec<Vec<FieldSet>>) -> FederationMetadata<'a> {
+        FederationMetadata {
+            type_name: "Product",
+            keys,
+            requires: Vec::new(),
+            provides: Vec::new(),
+            is_external: false,
+            is_extends: false,
+        }
+    }
+
+    fn representation(typename: &str, fields: serde_json::Value) -> Representation {
+        Representation {
+            typename: typename.to_string(),
+            fields: fields.as_object().cloned().unwrap(),
+        }
+    }
+
+    #[test]
+    fn unknown_typename_is_rejected() {
+        let entities = HashMap::new();
+        let representations = vec![representation("Product", json!({ "id": "1" }))];
+
+        let error = resolve_entities(&representations, &entities).unwrap_err();
+        assert!(matches!(error, EntityResolutionError::UnknownType(t) if t == "Product"));
+    }
+
+    #[test]
+    fn fields_matching_no_key_are_rejected() {
+        let mut entities = HashMap::new();
+        entities.insert("Product".to_string(), product_metadata(vec![vec![field("id")]]));
+        let representations = vec![representation("Product", json!({ "sku": "abc" }))];
+
+        let error = resolve_entities(&representations, &entities).unwrap_err();
+        assert!(matches!(error, EntityResolutionError::NoMatchingKey(t) if t == "Product"));
+    }
+
+    #[test]
+    fn flat_key_is_satisfied() {
+        let mut entities = HashMap::new();
+        entities.insert("Product".to_string(), product_metadata(vec![vec![field("id")]]));
+        let representations = vec![representation("Product", json!({ "id": "1" }))];
+
+        let requests = resolve_entities(&representations, &entities).unwrap();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].typename, "Product");
+        assert_eq!(requests[0].bound_fields.get("id").unwrap(), "1");
+    }
+
+    #[test]
+    fn nested_key_is_satisfied_by_a_matching_object() {
+        let org_key = FieldSet {
+            name: "org".to_string(),
+            selections: vec![field("id")],
+        };
+        let mut entities = HashMap::new();
+        entities.insert("Product".to_string(), product_metadata(vec![vec![org_key]]));
+        let representations = vec![representation(
+            "Product",
+            json!({ "org": { "id": "1" } }),
+        )];
+
+        let requests = resolve_entities(&representations, &entities).unwrap();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].bound_fields.get("org").unwrap(), &json!({ "id": "1" }));
+    }
+
+    #[test]
+    fn nested_key_rejects_a_bare_field_with_no_sub_selection() {
+        let org_key = FieldSet {
+            name: "org".to_string(),
+            selections: vec![field("id")],
+        };
+        let mut entities = HashMap::new();
+        entities.insert("Product".to_string(), product_metadata(vec![vec![org_key]]));
+        let representations = vec![representation("Product", json!({ "org": "acme" }))];
+
+        let error = resolve_entities(&representations, &entities).unwrap_err();
+        assert!(matches!(error, EntityResolutionError::NoMatchingKey(t) if t == "Product"));
+    }
+
+    #[test]
+    fn nested_key_rejects_an_object_missing_the_nested_field() {
+        let org_key = FieldSet {
+            name: "org".to_string(),
+            selections: vec![field("id")],
+        };
+        let mut entities = HashMap::new();
+        entities.insert("Product".to_string(), product_metadata(vec![vec![org_key]]));
+        let representations = vec![representation("Product", json!({ "org": {} }))];
+
+        let error = resolve_entities(&representations, &entities).unwrap_err();
+        assert!(matches!(error, EntityResolutionError::NoMatchingKey(t) if t == "Product"));
+    }
+}
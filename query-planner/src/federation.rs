@@ -1,28 +1,50 @@
+use crate::ast::schema::{Directive, Field, ObjectType, ObjectTypeExtension, TypeDefinition, Value};
 use crate::context::FieldSet;
-use graphql_parser::schema::{Field, ObjectType, TypeDefinition};
 
 pub struct FederationMetadata<'q> {
-    pub service_name: &'q str,
+    /// The name of the field, type or object this metadata was extracted from. This is
+    /// *not* the name of the subgraph/service that owns it — `get_federation_metadata`
+    /// is only ever handed a single schema element, so it has no service identifier to
+    /// report.
+    pub type_name: &'q str,
+    pub keys: Vec<Vec<FieldSet>>,
+    pub requires: Vec<FieldSet>,
+    pub provides: Vec<FieldSet>,
+    pub is_external: bool,
+    pub is_extends: bool,
 }
 
 impl<'q> FederationMetadata<'q> {
+    /// A value type is a type that's defined identically by every service that
+    /// references it, rather than owned and resolved by a single service. Federation
+    /// only needs to treat a type as an entity — resolvable through `_entities` — when
+    /// it declares a `@key`, or when it's an entity stub owned elsewhere (an
+    /// `@extends` directive, or a structural `extend type` block, both of which
+    /// `is_extends` already covers). Everything else is a value type that composition
+    /// can merge as-is.
     pub fn is_value_type(&self) -> bool {
-        unimplemented!()
+        self.keys.is_empty() && !self.is_extends
     }
 }
 
-pub enum SchemaRef<'q> {
-    FieldDef(&'q Field<'q>),
-    TypeDef(&'q TypeDefinition<'q>),
-    ObjType(&'q ObjectType<'q>),
+// `'a` is how long the caller's reference to the schema element is borrowed for;
+// `'q` is the lifetime of the string data the parsed document borrows from its
+// original source text. They're independent — a caller may only hold a short-lived
+// reference into a long-lived document — so `SchemaRef` must track both rather than
+// forcing the reference to live as long as the data it points at.
+pub enum SchemaRef<'a, 'q> {
+    FieldDef(&'a Field<'q>),
+    TypeDef(&'a TypeDefinition<'q>),
+    ObjType(&'a ObjectType<'q>),
+    ObjTypeExt(&'a ObjectTypeExtension<'q>),
 }
 
 macro_rules! impl_from {
     // This implements `From` for all inner types of SchemaRef,
     // so that get_federation_metadata can be called directly with any of those types.
     ($typ:ident < $lt:lifetime >, $enum_name:ident) => {
-        impl<$lt> From<&$lt$typ<$lt>> for SchemaRef<$lt> {
-            fn from(r: &$lt$typ<$lt>) -> Self {
+        impl<'a, $lt> From<&'a $typ<$lt>> for SchemaRef<'a, $lt> {
+            fn from(r: &'a $typ<$lt>) -> Self {
                 SchemaRef::$enum_name(r)
             }
         }
@@ -32,13 +54,236 @@ macro_rules! impl_from {
 impl_from!(Field<'q>, FieldDef);
 impl_from!(TypeDefinition<'q>, TypeDef);
 impl_from!(ObjectType<'q>, ObjType);
+impl_from!(ObjectTypeExtension<'q>, ObjTypeExt);
 
-pub fn get_federation_metadata<'q, T: Into<SchemaRef<'q>>>(
+pub fn get_federation_metadata<'a, 'q: 'a, T: Into<SchemaRef<'a, 'q>>>(
     handle: T,
 ) -> Option<FederationMetadata<'q>> {
     match handle.into() {
-        SchemaRef::FieldDef(field_def) => unimplemented!(),
-        SchemaRef::TypeDef(type_def) => unimplemented!(),
-        SchemaRef::ObjType(object_type) => unimplemented!(),
+        SchemaRef::FieldDef(field_def) => {
+            Some(build_metadata(field_def.name, &field_def.directives, false))
+        }
+        SchemaRef::TypeDef(type_def) => Some(build_metadata(
+            type_definition_name(type_def),
+            type_definition_directives(type_def),
+            false,
+        )),
+        SchemaRef::ObjType(object_type) => {
+            Some(build_metadata(object_type.name, &object_type.directives, false))
+        }
+        // A `extend type X` block is structurally an extension of an entity owned
+        // elsewhere, whether or not it also carries an explicit `@extends` directive
+        // (subgraph libraries that can't use the `extend` keyword rely on `@extends`
+        // instead, but both express the same fact).
+        SchemaRef::ObjTypeExt(object_type_ext) => Some(build_metadata(
+            object_type_ext.name,
+            &object_type_ext.directives,
+            true,
+        )),
+    }
+}
+
+fn build_metadata<'q>(
+    type_name: &'q str,
+    directives: &[Directive<'q>],
+    is_extension: bool,
+) -> FederationMetadata<'q> {
+    let mut keys = Vec::new();
+    let mut requires = Vec::new();
+    let mut provides = Vec::new();
+    let mut is_external = false;
+    let mut is_extends = is_extension;
+
+    for directive in directives {
+        match directive.name {
+            "key" => {
+                if let Some(fields) = fields_argument(directive) {
+                    keys.push(FieldSet::parse(fields));
+                }
+            }
+            "external" => is_external = true,
+            "requires" => {
+                if let Some(fields) = fields_argument(directive) {
+                    requires = FieldSet::parse(fields);
+                }
+            }
+            "provides" => {
+                if let Some(fields) = fields_argument(directive) {
+                    provides = FieldSet::parse(fields);
+                }
+            }
+            "extends" => is_extends = true,
+            _ => {}
+        }
+    }
+
+    FederationMetadata {
+        type_name,
+        keys,
+        requires,
+        provides,
+        is_external,
+        is_extends,
+    }
+}
+
+fn fields_argument<'a>(directive: &'a Directive) -> Option<&'a str> {
+    directive.arguments.iter().find_map(|(name, value)| {
+        if *name != "fields" {
+            return None;
+        }
+        match value {
+            Value::String(fields) => Some(fields.as_str()),
+            _ => None,
+        }
+    })
+}
+
+fn type_definition_name<'q>(type_def: &TypeDefinition<'q>) -> &'q str {
+    match type_def {
+        TypeDefinition::Scalar(t) => t.name,
+        TypeDefinition::Object(t) => t.name,
+        TypeDefinition::Interface(t) => t.name,
+        TypeDefinition::Union(t) => t.name,
+        TypeDefinition::Enum(t) => t.name,
+        TypeDefinition::InputObject(t) => t.name,
+    }
+}
+
+fn type_definition_directives<'a, 'q>(type_def: &'a TypeDefinition<'q>) -> &'a [Directive<'q>] {
+    match type_def {
+        TypeDefinition::Scalar(t) => &t.directives,
+        TypeDefinition::Object(t) => &t.directives,
+        TypeDefinition::Interface(t) => &t.directives,
+        TypeDefinition::Union(t) => &t.directives,
+        TypeDefinition::Enum(t) => &t.directives,
+        TypeDefinition::InputObject(t) => &t.directives,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::schema::{parse_schema, Definition, Document, TypeExtension};
+
+    #[test]
+    fn repeated_key_directives_produce_one_entry_per_key() {
+        let document = parse_schema(
+            r#"
+            type Product @key(fields: "id") @key(fields: "sku") {
+                id: ID!
+                sku: String!
+            }
+            "#,
+        )
+        .unwrap();
+
+        let product = document
+            .definitions
+            .iter()
+            .find_map(|definition| match definition {
+                Definition::TypeDefinition(TypeDefinition::Object(object)) if object.name == "Product" => {
+                    Some(object)
+                }
+                _ => None,
+            })
+            .unwrap();
+
+        let metadata = get_federation_metadata(product).unwrap();
+        assert_eq!(metadata.keys.len(), 2);
+        assert_eq!(metadata.keys[0][0].name, "id");
+        assert_eq!(metadata.keys[1][0].name, "sku");
     }
-}
\ No newline at end of file
+
+    fn object_named<'q>(document: &'q Document<'q>, name: &str) -> &'q ObjectType<'q> {
+        document
+            .definitions
+            .iter()
+            .find_map(|definition| match definition {
+                Definition::TypeDefinition(TypeDefinition::Object(object)) if object.name == name => {
+                    Some(object)
+                }
+                _ => None,
+            })
+            .unwrap()
+    }
+
+    fn object_extension_named<'q>(document: &'q Document<'q>, name: &str) -> &'q ObjectTypeExtension<'q> {
+        document
+            .definitions
+            .iter()
+            .find_map(|definition| match definition {
+                Definition::TypeExtension(TypeExtension::Object(extension)) if extension.name == name => {
+                    Some(extension)
+                }
+                _ => None,
+            })
+            .unwrap()
+    }
+
+    #[test]
+    fn entity_with_a_key_is_not_a_value_type() {
+        let document = parse_schema(
+            r#"
+            type Product @key(fields: "id") {
+                id: ID!
+            }
+            "#,
+        )
+        .unwrap();
+
+        let metadata = get_federation_metadata(object_named(&document, "Product")).unwrap();
+        assert!(!metadata.is_value_type());
+    }
+
+    #[test]
+    fn extends_stub_is_not_a_value_type() {
+        let document = parse_schema(
+            r#"
+            extend type Product @key(fields: "id") {
+                id: ID! @external
+            }
+            "#,
+        )
+        .unwrap();
+
+        let metadata =
+            get_federation_metadata(object_extension_named(&document, "Product")).unwrap();
+        assert!(!metadata.is_value_type());
+    }
+
+    /// A bare `extend type` block is a stub for an entity owned elsewhere even when it
+    /// carries neither a `@key` nor an explicit `@extends` directive — the `extend`
+    /// keyword itself is what marks it as an extension.
+    #[test]
+    fn bare_extend_type_with_no_key_or_extends_directive_is_not_a_value_type() {
+        let document = parse_schema(
+            r#"
+            extend type Product {
+                description: String
+            }
+            "#,
+        )
+        .unwrap();
+
+        let metadata =
+            get_federation_metadata(object_extension_named(&document, "Product")).unwrap();
+        assert!(!metadata.is_value_type());
+    }
+
+    #[test]
+    fn plain_type_with_no_key_is_a_value_type() {
+        let document = parse_schema(
+            r#"
+            type Money {
+                amount: Int!
+                currency: String!
+            }
+            "#,
+        )
+        .unwrap();
+
+        let metadata = get_federation_metadata(object_named(&document, "Money")).unwrap();
+        assert!(metadata.is_value_type());
+    }
+}
@@ -0,0 +1,5 @@
+mod ast;
+pub mod context;
+pub mod entities;
+pub mod federation;
+pub mod sdl;
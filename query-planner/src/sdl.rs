@@ -0,0 +1,262 @@
+use crate::ast::schema::{
+    Definition, Document, Field, InputValue, ObjectType, Pos, ScalarType, Type, TypeDefinition,
+    TypeExtension, UnionType,
+};
+use crate::federation::{get_federation_metadata, FederationMetadata};
+use std::collections::HashSet;
+
+/// Renders a parsed service schema as a federation subgraph SDL: the schema's own
+/// types and directives, plus the federation scaffolding every subgraph must expose
+/// (`_Any`, `_FieldSet`, `_Service` and `Query._service`), and — when the schema
+/// declares at least one `@key` type — the `_Entity` union and `Query._entities`.
+///
+/// `@key`/`@external`/`@requires`/`@provides`/`@extends` directives don't need any
+/// special handling here — they're already part of the parsed `document`, so
+/// `graphql_parser`'s own printer re-renders them onto their fields and types as-is.
+/// A gateway is expected to wire `Query._service.sdl` to return this same service's
+/// original schema text, so it can introspect and compose this subgraph.
+pub fn export_sdl<'q>(document: &Document<'q>) -> String {
+    let mut document = document.clone();
+    add_federation_scaffolding(&mut document);
+    document.to_string()
+}
+
+fn add_federation_scaffolding<'q>(document: &mut Document<'q>) {
+    document
+        .definitions
+        .push(Definition::TypeDefinition(TypeDefinition::Scalar(scalar("_Any"))));
+    document
+        .definitions
+        .push(Definition::TypeDefinition(TypeDefinition::Scalar(scalar("_FieldSet"))));
+    document
+        .definitions
+        .push(Definition::TypeDefinition(TypeDefinition::Object(ObjectType {
+            position: Pos::default(),
+            description: None,
+            name: "_Service",
+            implements_interfaces: Vec::new(),
+            directives: Vec::new(),
+            fields: vec![named_field("sdl", Type::NamedType("String"))],
+        })));
+
+    let mut query_fields = vec![service_field()];
+
+    // An empty `_Entity` union is invalid under GraphQL's union type-validation rules,
+    // so the union and the `_entities` root field only get added when at least one
+    // type in this service declares a `@key`, whether via `type ... @key` or a
+    // `extend type ... @key` entity stub.
+    let entity_type_names = entity_type_names(document);
+    if !entity_type_names.is_empty() {
+        document
+            .definitions
+            .push(Definition::TypeDefinition(TypeDefinition::Union(UnionType {
+                position: Pos::default(),
+                description: None,
+                name: "_Entity",
+                directives: Vec::new(),
+                types: entity_type_names,
+            })));
+        query_fields.push(entities_field());
+    }
+
+    let query_type_name = query_type_name(document);
+    match find_query_fields(document, query_type_name) {
+        Some(fields) => fields.extend(query_fields),
+        None => document
+            .definitions
+            .push(Definition::TypeDefinition(TypeDefinition::Object(ObjectType {
+                position: Pos::default(),
+                description: None,
+                name: query_type_name,
+                implements_interfaces: Vec::new(),
+                directives: Vec::new(),
+                fields: query_fields,
+            }))),
+    }
+}
+
+/// The name of the root query type, as declared by an explicit `schema { query: ... }`
+/// definition, or `Query` per the GraphQL default.
+fn query_type_name<'q>(document: &Document<'q>) -> &'q str {
+    document
+        .definitions
+        .iter()
+        .find_map(|definition| match definition {
+            Definition::SchemaDefinition(schema) => schema.query,
+            _ => None,
+        })
+        .unwrap_or("Query")
+}
+
+fn entity_type_names<'q>(document: &Document<'q>) -> Vec<&'q str> {
+    let mut seen = HashSet::new();
+    document
+        .definitions
+        .iter()
+        .filter_map(|definition| match definition {
+            Definition::TypeDefinition(TypeDefinition::Object(object)) => {
+                has_key(get_federation_metadata(object)).then_some(object.name)
+            }
+            Definition::TypeExtension(TypeExtension::Object(extension)) => {
+                has_key(get_federation_metadata(extension)).then_some(extension.name)
+            }
+            _ => None,
+        })
+        .filter(|name| seen.insert(*name))
+        .collect()
+}
+
+fn has_key(metadata: Option<FederationMetadata>) -> bool {
+    metadata.map(|metadata| !metadata.keys.is_empty()).unwrap_or(false)
+}
+
+/// Finds the field list to extend with federation's root fields: either an existing
+/// `type <query_type_name>` definition or a matching `extend type` (federation
+/// subgraphs very commonly declare their query root as an extension). Returns `None`
+/// only when the root type isn't declared at all, so the caller can synthesize one.
+fn find_query_fields<'a, 'q>(
+    document: &'a mut Document<'q>,
+    query_type_name: &str,
+) -> Option<&'a mut Vec<Field<'q>>> {
+    document.definitions.iter_mut().find_map(|definition| match definition {
+        Definition::TypeDefinition(TypeDefinition::Object(object)) if object.name == query_type_name => {
+            Some(&mut object.fields)
+        }
+        Definition::TypeExtension(TypeExtension::Object(extension))
+            if extension.name == query_type_name =>
+        {
+            Some(&mut extension.fields)
+        }
+        _ => None,
+    })
+}
+
+fn service_field<'q>() -> Field<'q> {
+    named_field(
+        "_service",
+        Type::NonNullType(Box::new(Type::NamedType("_Service"))),
+    )
+}
+
+fn entities_field<'q>() -> Field<'q> {
+    let mut field = named_field(
+        "_entities",
+        Type::NonNullType(Box::new(Type::ListType(Box::new(Type::NamedType("_Entity"))))),
+    );
+    field.arguments.push(InputValue {
+        position: Pos::default(),
+        description: None,
+        name: "representations",
+        value_type: Type::NonNullType(Box::new(Type::ListType(Box::new(Type::NonNullType(
+            Box::new(Type::NamedType("_Any")),
+        ))))),
+        default_value: None,
+        directives: Vec::new(),
+    });
+    field
+}
+
+fn named_field<'q>(name: &'q str, field_type: Type<'q>) -> Field<'q> {
+    Field {
+        position: Pos::default(),
+        description: None,
+        name,
+        arguments: Vec::new(),
+        field_type,
+        directives: Vec::new(),
+    }
+}
+
+fn scalar<'q>(name: &'q str) -> ScalarType<'q> {
+    ScalarType {
+        position: Pos::default(),
+        description: None,
+        name,
+        directives: Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::schema::parse_schema;
+
+    #[test]
+    fn schema_with_no_key_gets_scaffolding_but_no_entity_union() {
+        let document = parse_schema(
+            r#"
+            type Query {
+                money: Money
+            }
+
+            type Money {
+                amount: Int!
+            }
+            "#,
+        )
+        .unwrap();
+
+        let sdl = export_sdl(&document);
+
+        assert!(sdl.contains("scalar _Any"));
+        assert!(sdl.contains("scalar _FieldSet"));
+        assert!(sdl.contains("type _Service"));
+        assert!(sdl.contains("_service: _Service!"));
+        assert!(!sdl.contains("union _Entity"));
+        assert!(!sdl.contains("_entities"));
+    }
+
+    #[test]
+    fn extends_the_existing_extend_type_query_root_instead_of_duplicating_it() {
+        let document = parse_schema(
+            r#"
+            extend type Query {
+                me: User
+            }
+
+            type User @key(fields: "id") {
+                id: ID!
+            }
+            "#,
+        )
+        .unwrap();
+
+        let sdl = export_sdl(&document);
+
+        assert_eq!(sdl.matches("extend type Query").count(), 1);
+        assert_eq!(sdl.matches("Query {").count(), 1);
+        assert!(sdl.contains("me: User"));
+        assert!(sdl.contains("_service: _Service!"));
+        assert!(sdl.contains("_entities(representations: [_Any!]!): [_Entity]!"));
+    }
+
+    #[test]
+    fn duplicate_entity_stubs_for_the_same_type_yield_a_single_union_member() {
+        let document = parse_schema(
+            r#"
+            type Query {
+                products: [Product!]!
+            }
+
+            type Product @key(fields: "id") {
+                id: ID!
+                name: String!
+            }
+
+            extend type Product @key(fields: "id") {
+                id: ID! @external
+                price: Int! @requires(fields: "id")
+            }
+            "#,
+        )
+        .unwrap();
+
+        let sdl = export_sdl(&document);
+
+        let union_line = sdl
+            .lines()
+            .find(|line| line.contains("union _Entity"))
+            .unwrap();
+        assert_eq!(union_line.trim(), "union _Entity = Product");
+    }
+}